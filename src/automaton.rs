@@ -0,0 +1,84 @@
+//! An [`fst::Automaton`] over 5-letter Wordle candidates.
+//!
+//! A plain per-position regex class (`[abc][def]...`) can tell you which
+//! letters are allowed at each position, but it can't express "this word
+//! contains at least two `e`s" — the kind of constraint you get for free
+//! when a single day's grid shows two greens on the same letter.
+//! [`CandidateAutomaton`] walks the FST byte-by-byte, tracking both the
+//! current position and a running per-letter count, so both kinds of
+//! constraint can be enforced during the search instead of after it.
+
+use fst::Automaton;
+
+const WORD_LEN: u8 = 5;
+const DEAD: u8 = u8::MAX;
+
+/// Per-position allowed-letter table plus the minimum number of times each
+/// letter must appear for a candidate to match.
+pub struct CandidateAutomaton {
+    allowed: [[bool; 26]; 5],
+    min_counts: [u8; 26],
+}
+
+impl CandidateAutomaton {
+    pub fn new(allowed: [[bool; 26]; 5], min_counts: [u8; 26]) -> Self {
+        Self {
+            allowed,
+            min_counts,
+        }
+    }
+}
+
+/// `position == DEAD` marks a dead state; no candidate can match from there.
+#[derive(Clone, Copy, Debug)]
+pub struct CandidateState {
+    position: u8,
+    counts: [u8; 26],
+}
+
+impl Automaton for CandidateAutomaton {
+    type State = CandidateState;
+
+    fn start(&self) -> Self::State {
+        CandidateState {
+            position: 0,
+            counts: [0; 26],
+        }
+    }
+
+    fn is_match(&self, state: &Self::State) -> bool {
+        state.position == WORD_LEN
+            && self
+                .min_counts
+                .iter()
+                .zip(state.counts.iter())
+                .all(|(&min, &got)| got >= min)
+    }
+
+    fn can_match(&self, state: &Self::State) -> bool {
+        state.position != DEAD
+    }
+
+    fn accept(&self, state: &Self::State, byte: u8) -> Self::State {
+        let dead = CandidateState {
+            position: DEAD,
+            counts: state.counts,
+        };
+
+        if state.position >= WORD_LEN || !byte.is_ascii_lowercase() {
+            return dead;
+        }
+
+        let idx = (byte - b'a') as usize;
+        if !self.allowed[state.position as usize][idx] {
+            return dead;
+        }
+
+        let mut counts = state.counts;
+        counts[idx] += 1;
+        CandidateState {
+            position: state.position + 1,
+            counts,
+        }
+    }
+}
@@ -0,0 +1,301 @@
+//! A small filter expression language for choosing which crawled days feed
+//! into `solve`, replacing the flat `--excludes <day>...` list.
+//!
+//! A [`DaySelector`] is one or more whitespace-separated clauses, ANDed
+//! together:
+//!
+//! - `216..221` — a day range (`Range`)
+//! - `>215` / `<215` — days after/before a given day (also `Range`)
+//! - `include 7,14,21` — only these days (`Only`)
+//! - `exclude 7,14,21` — all but these days (`Exclude`)
+//! - `after 2022-01-01` / `before 2022-01-01` — by the tweet's date
+//!   (`After`/`Before`)
+
+use std::{collections::HashSet, str::FromStr};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+
+/// One filter node in a day-selection expression.
+#[derive(Debug, Clone)]
+enum DayFilter {
+    /// `start..end`, half-open like a Rust range.
+    Range(usize, usize),
+    Before(DateTime<Utc>),
+    After(DateTime<Utc>),
+    Only(HashSet<usize>),
+    Exclude(HashSet<usize>),
+}
+
+impl DayFilter {
+    fn matches(&self, day: usize, created_at: DateTime<Utc>) -> bool {
+        match self {
+            DayFilter::Range(start, end) => (*start..*end).contains(&day),
+            DayFilter::Before(t) => created_at < *t,
+            DayFilter::After(t) => created_at > *t,
+            DayFilter::Only(days) => days.contains(&day),
+            DayFilter::Exclude(days) => !days.contains(&day),
+        }
+    }
+}
+
+/// A parsed day-selection expression: a day is selected iff every clause
+/// matches it.
+#[derive(Debug, Clone, Default)]
+pub struct DaySelector(Vec<DayFilter>);
+
+impl DaySelector {
+    pub fn matches(&self, day: usize, created_at: DateTime<Utc>) -> bool {
+        self.0.iter().all(|clause| clause.matches(day, created_at))
+    }
+}
+
+impl FromStr for DaySelector {
+    type Err = color_eyre::eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let tokens = tokenize(s)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            pos: 0,
+        };
+
+        let mut clauses = Vec::new();
+        while parser.peek().is_some() {
+            clauses.push(parser.parse_clause()?);
+        }
+
+        Ok(DaySelector(clauses))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(usize),
+    DotDot,
+    Comma,
+    Gt,
+    Lt,
+    Word(String),
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(i, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            ',' => {
+                chars.next();
+                tokens.push(Token::Comma);
+            }
+            '>' => {
+                chars.next();
+                tokens.push(Token::Gt);
+            }
+            '<' => {
+                chars.next();
+                tokens.push(Token::Lt);
+            }
+            '.' => {
+                chars.next();
+                if chars.next_if(|&(_, c)| c == '.').is_some() {
+                    tokens.push(Token::DotDot);
+                } else {
+                    return Err(eyre!("expected `..` at byte {i} of `{input}`"));
+                }
+            }
+            c if c.is_ascii_digit() => {
+                let start = i;
+                while chars.next_if(|&(_, c)| c.is_ascii_digit()).is_some() {}
+
+                // A `-` right after a digit run means this isn't a bare day
+                // number but the start of an ISO-8601 date/datetime (e.g.
+                // `2022-01-01` or `2022-01-01T00:00:00Z`) — keep consuming
+                // through the rest of it instead of splitting on `-`/`:`/`T`.
+                if matches!(chars.peek(), Some(&(_, '-'))) {
+                    while chars
+                        .next_if(|&(_, c)| {
+                            c.is_ascii_digit() || matches!(c, '-' | ':' | 'T' | 't' | 'Z' | 'z' | '+' | '.')
+                        })
+                        .is_some()
+                    {}
+                    let end = chars.peek().map_or(input.len(), |&(j, _)| j);
+                    tokens.push(Token::Word(input[start..end].to_string()));
+                } else {
+                    let end = chars.peek().map_or(input.len(), |&(j, _)| j);
+                    tokens.push(Token::Number(input[start..end].parse().wrap_err_with(
+                        || format!("`{}` isn't a valid day number", &input[start..end]),
+                    )?));
+                }
+            }
+            _ => {
+                let start = i;
+                while chars
+                    .next_if(|&(_, c)| !c.is_whitespace() && !matches!(c, ',' | '>' | '<'))
+                    .is_some()
+                {}
+                let end = chars.peek().map_or(input.len(), |&(j, _)| j);
+                tokens.push(Token::Word(input[start..end].to_string()));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn bump(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    fn parse_number(&mut self) -> Result<usize> {
+        match self.bump() {
+            Some(&Token::Number(n)) => Ok(n),
+            other => Err(eyre!("expected a day number, got {other:?}")),
+        }
+    }
+
+    fn parse_number_list(&mut self) -> Result<HashSet<usize>> {
+        let mut days = HashSet::new();
+        days.insert(self.parse_number()?);
+        while matches!(self.peek(), Some(Token::Comma)) {
+            self.bump();
+            days.insert(self.parse_number()?);
+        }
+        Ok(days)
+    }
+
+    fn parse_date(&mut self) -> Result<DateTime<Utc>> {
+        match self.bump() {
+            Some(Token::Word(w)) => parse_date(w),
+            other => Err(eyre!("expected a date (e.g. `2022-01-01`), got {other:?}")),
+        }
+    }
+
+    fn parse_clause(&mut self) -> Result<DayFilter> {
+        match self.bump().cloned() {
+            Some(Token::Number(start)) => {
+                if matches!(self.peek(), Some(Token::DotDot)) {
+                    self.bump();
+                    Ok(DayFilter::Range(start, self.parse_number()?))
+                } else {
+                    Ok(DayFilter::Only(HashSet::from([start])))
+                }
+            }
+            Some(Token::Gt) => Ok(DayFilter::Range(self.parse_number()? + 1, usize::MAX)),
+            Some(Token::Lt) => Ok(DayFilter::Range(0, self.parse_number()?)),
+            Some(Token::Word(w)) if w.eq_ignore_ascii_case("include") => {
+                Ok(DayFilter::Only(self.parse_number_list()?))
+            }
+            Some(Token::Word(w)) if w.eq_ignore_ascii_case("exclude") => {
+                Ok(DayFilter::Exclude(self.parse_number_list()?))
+            }
+            Some(Token::Word(w)) if w.eq_ignore_ascii_case("after") => {
+                Ok(DayFilter::After(self.parse_date()?))
+            }
+            Some(Token::Word(w)) if w.eq_ignore_ascii_case("before") => {
+                Ok(DayFilter::Before(self.parse_date()?))
+            }
+            other => Err(eyre!(
+                "unexpected token in day-selection expression: {other:?}"
+            )),
+        }
+    }
+}
+
+fn parse_date(s: &str) -> Result<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+
+    let date = NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .wrap_err_with(|| format!("`{s}` isn't a recognized date (expected e.g. `2022-01-01`)"))?;
+    Ok(date.and_hms_opt(0, 0, 0).unwrap().and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> DaySelector {
+        s.parse().unwrap()
+    }
+
+    fn epoch() -> DateTime<Utc> {
+        "1970-01-01T00:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn range() {
+        let DaySelector(clauses) = parse("216..221");
+        assert_eq!(clauses.len(), 1);
+        assert!(matches!(clauses[0], DayFilter::Range(216, 221)));
+
+        // Half-open: the end of the range is excluded.
+        assert!(parse("216..221").matches(220, epoch()));
+        assert!(!parse("216..221").matches(221, epoch()));
+    }
+
+    #[test]
+    fn gt_lt() {
+        let selector = parse(">215");
+        assert!(!selector.matches(215, epoch()));
+        assert!(selector.matches(216, epoch()));
+
+        let selector = parse("<215");
+        assert!(selector.matches(214, epoch()));
+        assert!(!selector.matches(215, epoch()));
+    }
+
+    #[test]
+    fn include_exclude() {
+        let selector = parse("include 7,14,21");
+        assert!(selector.matches(14, epoch()));
+        assert!(!selector.matches(15, epoch()));
+
+        let selector = parse("exclude 0");
+        assert!(!selector.matches(0, epoch()));
+        assert!(selector.matches(1, epoch()));
+    }
+
+    #[test]
+    fn after_date() {
+        let DaySelector(clauses) = parse("after 2022-01-01");
+        assert!(matches!(clauses[0], DayFilter::After(_)));
+
+        let selector = parse("after 2022-01-01");
+        assert!(selector.matches(0, "2022-01-02T00:00:00Z".parse().unwrap()));
+        assert!(!selector.matches(0, "2021-12-31T00:00:00Z".parse().unwrap()));
+    }
+
+    #[test]
+    fn rfc3339_datetime_round_trips() {
+        let tokens = tokenize("2022-01-01T00:00:00Z").unwrap();
+        assert_eq!(
+            tokens,
+            [Token::Word("2022-01-01T00:00:00Z".to_string())]
+        );
+
+        let got = parse_date("2022-01-01T00:00:00Z").unwrap();
+        let expected: DateTime<Utc> = "2022-01-01T00:00:00Z".parse().unwrap();
+        assert_eq!(got, expected);
+    }
+}
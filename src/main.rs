@@ -1,22 +1,41 @@
 use std::{
     collections::HashSet,
     fmt::{self, Debug, Display},
+    io::BufRead,
+    path::PathBuf,
     str::FromStr,
+    sync::OnceLock,
 };
 
 use chrono::{DateTime, Utc};
 use color_eyre::{eyre::WrapErr, owo_colors::OwoColorize, Help, Result};
 use egg_mode::{auth, tweet, KeyPair};
+use fst::{IntoStreamer, Streamer};
 use futures::StreamExt;
 use lazy_static::lazy_static;
-use regex::Regex;
-use reqwest::Url;
-use soup::{NodeExt, QueryBuilderExt, Soup};
 use structopt::StructOpt;
 use tokio::runtime::Handle;
 
+mod automaton;
+mod day_filter;
+mod ranking;
+mod word_source;
+use automaton::CandidateAutomaton;
+use day_filter::DaySelector;
+use word_source::{ResolvedWordSource, WordSource};
+
+#[derive(Debug, StructOpt)]
+enum Args {
+    /// Crawl a Twitter thread for Wordle grids and infer the first guess.
+    Crawl(CrawlArgs),
+
+    /// Manually enter each day's answer and emoji grid, skipping Twitter
+    /// entirely, and watch the candidate first-guess set narrow live.
+    Interactive(InteractiveArgs),
+}
+
 #[derive(Debug, StructOpt)]
-struct Args {
+struct CrawlArgs {
     /// The root of the twitter thread to crawl.
     root_tweet_id: u64,
 
@@ -32,70 +51,80 @@ struct Args {
     #[structopt(long, env = "TWITTER_CONSUMER_SECRET")]
     consumer_secret: String,
 
-    /// Days to exclude.
-    #[structopt(short, long, default_value = "0")]
-    excludes: Vec<usize>,
+    /// A day-selection filter expression: clauses like `216..221`, `>215`,
+    /// `include 7,14,21`, `exclude 7,14,21`, or `after 2022-01-01`, combined
+    /// with AND. `start..end` is half-open like a Rust range, so `216..221`
+    /// excludes day 221 — use `216..222` to include it. See the `day_filter`
+    /// module docs for the full grammar.
+    #[structopt(short, long, default_value = "exclude 0")]
+    days: DaySelector,
+
+    #[structopt(flatten)]
+    word_source_args: WordSourceArgs,
 }
 
-const URL: &str = "https://www.powerlanguage.co.uk/wordle/";
-const START_DATE: &str = "2021-06-19T00:00:00Z";
+#[derive(Debug, StructOpt)]
+struct InteractiveArgs {
+    #[structopt(flatten)]
+    word_source_args: WordSourceArgs,
+}
 
-async fn get_valid_words_and_answers() -> (Vec<String>, Vec<String>) {
-    let url = Url::parse(URL).unwrap();
-    let main_page = reqwest::get(url.clone())
-        .await
-        .unwrap()
-        .text()
-        .await
-        .unwrap();
-
-    let script = Soup::new(&main_page)
-        .tag("script")
-        .find_all()
-        .filter_map(|script| script.get("src"))
-        .filter(|src| src.starts_with("main"))
-        .last()
-        .expect("main script on the wordle page");
-
-    let script_url = url.join(&script).unwrap();
-    let script = reqwest::get(script_url)
-        .await
-        .unwrap()
-        .text()
-        .await
-        .unwrap();
-
-    const DAY1_ANS: &str = "cigar";
-    let answers_starting_idx = script.find(&format!("[\"{DAY1_ANS}\",")).unwrap();
-    let data = &script[answers_starting_idx..];
-    let answers_ending_idx = data.find(']').unwrap();
-    let answers = &data[1..answers_ending_idx];
-
-    let word_list_starting_idx = (&data[1..]).find('[').unwrap();
-    let word_list = &data[1 + word_list_starting_idx..];
-    let word_list_ending_idx = word_list.find(']').unwrap();
-    let word_list = &word_list[1..word_list_ending_idx];
-
-    let parse = |s: &str| {
-        debug_assert!(s.len() == 7 && &s[0..1] == "\"" && &s[6..7] == "\"");
-        s[1..=5].to_string()
-    };
+#[derive(Debug, StructOpt)]
+struct WordSourceArgs {
+    /// Where to load the valid-word/answer lists from: `scrape` to scrape
+    /// the live Wordle page, or a path to a local word-list file (see
+    /// `word_source::WordSource::File`).
+    #[structopt(long, default_value = "scrape")]
+    word_source: WordSource,
+
+    /// Directory to cache scraped word lists in, so the tool keeps working
+    /// (from cache, then from a bundled snapshot) if the scrape breaks.
+    ///
+    /// Defaults to the platform cache dir (e.g. `~/.cache/wordle-thing` on
+    /// Linux).
+    #[structopt(long, parse(from_os_str))]
+    cache_dir: Option<PathBuf>,
+}
 
-    (
-        word_list.split(',').map(parse).collect::<Vec<_>>(),
-        answers.split(',').map(parse).collect::<Vec<_>>(),
-    )
+impl WordSourceArgs {
+    /// Configures `WORDLE_DATA`'s word source from these args. Must be
+    /// called before `WORDLE_DATA` is first accessed.
+    fn configure(self) {
+        let cache_dir = self
+            .cache_dir
+            .unwrap_or_else(word_source::default_cache_dir);
+        configure_word_source(ResolvedWordSource::new(
+            self.word_source,
+            cache_dir,
+            word_source::DEFAULT_CACHE_TTL,
+        ));
+    }
 }
 
+const START_DATE: &str = "2021-06-19T00:00:00Z";
+
 struct WordleData {
     valid_words: Vec<String>,
-    answers: Vec<String>,
+    pub(crate) answers: Vec<String>,
+}
+
+static WORD_SOURCE: OnceLock<ResolvedWordSource> = OnceLock::new();
+
+/// Configures where `WORDLE_DATA` loads its word lists from. Must be called
+/// before `WORDLE_DATA` is first accessed; if it's never called,
+/// `WORDLE_DATA` falls back to `ResolvedWordSource::default()`.
+fn configure_word_source(source: ResolvedWordSource) {
+    WORD_SOURCE
+        .set(source)
+        .unwrap_or_else(|_| panic!("word source configured more than once"));
 }
 
 lazy_static! {
-    static ref WORDLE_DATA: WordleData = tokio::task::block_in_place(|| {
-        let (valid_words, answers) =
-            Handle::current().block_on(async move { get_valid_words_and_answers().await });
+    pub(crate) static ref WORDLE_DATA: WordleData = tokio::task::block_in_place(|| {
+        let source = WORD_SOURCE.get_or_init(ResolvedWordSource::default);
+        let (valid_words, answers) = Handle::current()
+            .block_on(async move { source.fetch().await })
+            .expect("failed to load the valid-word/answer lists");
         WordleData {
             valid_words,
             answers,
@@ -103,8 +132,34 @@ lazy_static! {
     });
 }
 
+/// The sorted, deduped union of `valid_words` and `answers`, plus an `fst`
+/// built over the same words. `fst::Set` requires its input sorted, so we
+/// also keep the `Vec` around to map matches back to `&'static str`s.
+struct WordSet {
+    set: fst::Set<Vec<u8>>,
+    words: Vec<&'static str>,
+}
+
+lazy_static! {
+    static ref WORD_SET: WordSet = {
+        let mut words: Vec<&'static str> = WORDLE_DATA
+            .valid_words
+            .iter()
+            .chain(WORDLE_DATA.answers.iter())
+            .map(|w| &**w)
+            .collect();
+        words.sort_unstable();
+        words.dedup();
+
+        let set = fst::Set::from_iter(words.iter().copied())
+            .expect("valid_words/answers are sorted and deduped");
+
+        WordSet { set, words }
+    };
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-enum Cell {
+pub(crate) enum Cell {
     Partial,
     Match,
     Nop,
@@ -157,7 +212,7 @@ impl GuessDisplay for Guess {
     }
 }
 
-type Guess = [Cell; 5];
+pub(crate) type Guess = [Cell; 5];
 
 #[derive(Clone)]
 enum Constraint {
@@ -231,46 +286,67 @@ fn solve(guesses: &[(Guess, &str)]) -> Option<Vec<&'static str>> {
 
     // dbg!(&constraints);
 
-    // Next, solve for each letter:
+    // Next, solve for each position's set of allowed letters:
     let mut impossible = false;
-    let regex = constraints
-        .iter()
-        .map(|constraints| {
-            constraints.iter().fold(
-                ('a'..='z').collect::<HashSet<_>>(),
-                |state_space, c| match c {
-                    Constraint::IsOneOf(h) => state_space.intersection(h).copied().collect(),
-                    Constraint::IsNoneOf(h) => state_space.difference(h).copied().collect(),
-                },
-            )
-        })
-        .enumerate()
-        .map(|(idx, allowed)| {
-            if allowed.len() == 0 {
-                println!(":-( no possible values for letter {}", idx + 1);
-                impossible = true;
-            }
-            let mut allowed = allowed.into_iter().collect::<Vec<_>>();
-            allowed.sort();
-            format!("[{}]", allowed.iter().collect::<String>())
-        })
-        .collect::<String>();
+    let mut allowed_table = [[false; 26]; 5];
+    for (idx, allowed) in constraints.iter().enumerate() {
+        let allowed = allowed.iter().fold(
+            ('a'..='z').collect::<HashSet<_>>(),
+            |state_space, c| match c {
+                Constraint::IsOneOf(h) => state_space.intersection(h).copied().collect(),
+                Constraint::IsNoneOf(h) => state_space.difference(h).copied().collect(),
+            },
+        );
+
+        if allowed.is_empty() {
+            println!(":-( no possible values for letter {}", idx + 1);
+            impossible = true;
+        }
+
+        for c in allowed {
+            allowed_table[idx][(c as u8 - b'a') as usize] = true;
+        }
+    }
 
     if impossible {
         return None;
     }
 
-    println!("Using regex: `{regex}`.");
-
-    let re = Regex::new(&format!("^{}$", regex)).unwrap();
-    let possible_guesses: Vec<_> = WORDLE_DATA
-        .valid_words
-        .iter()
-        .chain(WORDLE_DATA.answers.iter())
-        .map(|w| &**w)
-        .filter(|w| re.is_match(w))
-        .collect();
-    if possible_guesses.len() == 0 {
+    // Derive per-letter minimum occurrence counts: for each day, count how
+    // many Match (green) cells land on a given letter of that day's word,
+    // then keep the running max across days. This is what lets us catch
+    // e.g. two greens on the same letter, which a per-position
+    // allowed-letter set alone can't express.
+    //
+    // Partial (yellow) cells can't contribute here: a yellow at position
+    // `i` only tells us the guessed letter is some answer letter *other
+    // than* the one at position `i`, not that the guess contains the
+    // answer's letter at position `i`. Counting it in would wrongly
+    // require every candidate to contain that letter.
+    let mut min_counts = [0u8; 26];
+    for (guess, word) in guesses {
+        let chars: [char; 5] = word.chars().collect::<Vec<_>>().try_into().unwrap();
+        let mut day_counts = [0u8; 26];
+        for (i, &cell) in guess.iter().enumerate() {
+            if matches!(cell, Cell::Match) {
+                day_counts[(chars[i] as u8 - b'a') as usize] += 1;
+            }
+        }
+        for (min, day) in min_counts.iter_mut().zip(day_counts) {
+            *min = (*min).max(day);
+        }
+    }
+
+    let automaton = CandidateAutomaton::new(allowed_table, min_counts);
+    let mut stream = WORD_SET.set.search(&automaton).into_stream();
+    let mut possible_guesses = Vec::new();
+    while let Some(key) = stream.next() {
+        let word = std::str::from_utf8(key).unwrap();
+        let idx = WORD_SET.words.binary_search(&word).unwrap();
+        possible_guesses.push(WORD_SET.words[idx]);
+    }
+
+    if possible_guesses.is_empty() {
         return None;
     }
 
@@ -280,8 +356,16 @@ fn solve(guesses: &[(Guess, &str)]) -> Option<Vec<&'static str>> {
 #[tokio::main]
 async fn main() -> Result<()> {
     color_eyre::install()?;
-    let args = Args::from_args();
-    let excludes = args.excludes.iter().collect::<HashSet<_>>();
+
+    match Args::from_args() {
+        Args::Crawl(args) => crawl(args).await,
+        Args::Interactive(args) => interactive(args).await,
+    }
+}
+
+async fn crawl(args: CrawlArgs) -> Result<()> {
+    let days = args.days;
+    args.word_source_args.configure();
 
     let token = KeyPair::new(args.consumer_key, args.consumer_secret);
     let token = auth::bearer_token(&token)
@@ -353,6 +437,8 @@ async fn main() -> Result<()> {
             continue;
         };
 
+        let author_date = t.created_at.unwrap();
+
         // If the tweet starts with "Wordle <day number>" we'll use that day number.
         let day: usize = if let Some(day) = text
             .lines()
@@ -364,14 +450,13 @@ async fn main() -> Result<()> {
             day
         } else {
             // Otherwise we'll guess from the tweet date.
-            let author_date = t.created_at.unwrap();
             author_date
                 .signed_duration_since(day_one)
                 .num_days()
                 .try_into()
                 .unwrap()
         };
-        if excludes.contains(&day) {
+        if !days.matches(day, author_date) {
             continue;
         }
 
@@ -385,13 +470,14 @@ async fn main() -> Result<()> {
         println!();
         if potential_answers.len() == 1 {
             println!("Is your first guess.. {}?", potential_answers[0].bold());
-        } else if potential_answers.len() <= 12 {
-            println!("Couldn't exactly figure out your preferred first guess but we have some guesses: {:#?}", potential_answers);
         } else {
             println!(
-                "Couldn't figure it out! (we found {} possibilities, too many)",
-                potential_answers.len()
+                "Couldn't exactly figure out your preferred first guess, but here are the \
+                candidates ranked by expected information gain:"
             );
+            for (word, bits) in ranking::rank_candidates(&potential_answers) {
+                println!("  {} ({bits:.3} bits)", word.bold());
+            }
         }
     } else {
         std::process::exit(2);
@@ -400,13 +486,117 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+async fn interactive(args: InteractiveArgs) -> Result<()> {
+    args.word_source_args.configure();
+
+    println!(
+        "Enter each day as `<day number or answer> <emoji grid>` (e.g. `221 🟨⬛⬛⬛⬛`).\n\
+        An empty line stops."
+    );
+
+    let mut guesses: Vec<(Guess, &str)> = Vec::new();
+    for line in std::io::stdin().lock().lines() {
+        let line = line.wrap_err("failed to read from stdin")?;
+        let line = line.trim();
+        if line.is_empty() {
+            break;
+        }
+
+        let (day_or_word, grid) = if let Some(split) = line.split_once(char::is_whitespace) {
+            split
+        } else {
+            eprintln!("expected `<day number or answer> <emoji grid>`, got `{line}`");
+            continue;
+        };
+
+        let word = if let Ok(day) = day_or_word.parse::<usize>() {
+            if let Some(word) = WORDLE_DATA.answers.get(day) {
+                &**word
+            } else {
+                eprintln!(
+                    "day {day} is out of range (only {} days known)",
+                    WORDLE_DATA.answers.len()
+                );
+                continue;
+            }
+        } else if let Some(word) = WORDLE_DATA
+            .answers
+            .iter()
+            .find(|w| w.as_str() == day_or_word)
+        {
+            &**word
+        } else {
+            eprintln!("`{day_or_word}` isn't a known Wordle answer or day number");
+            continue;
+        };
+
+        let cells = grid
+            .trim()
+            .chars()
+            .map(Cell::try_from)
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .ok()
+            .and_then(|cells| <[Cell; 5]>::try_from(cells).ok());
+        let cells = if let Some(cells) = cells {
+            cells
+        } else {
+            eprintln!("expected a 5-cell emoji grid (⬛🟨🟩), got `{grid}`");
+            continue;
+        };
+
+        guesses.push((cells, word));
+
+        match solve(&guesses) {
+            Some(candidates) if candidates.len() == 1 => {
+                println!("Is your first guess.. {}?", candidates[0].bold());
+            }
+            Some(candidates) => {
+                println!("Candidates so far, ranked by expected information gain:");
+                for (word, bits) in ranking::rank_candidates(&candidates) {
+                    println!("  {} ({bits:.3} bits)", word.bold());
+                }
+            }
+            None => {
+                println!(":-( these entries are contradictory");
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     //! https://www.devangthakkar.com/wordle_archive/?221 is useful for
     //! making test cases.
 
+    use std::sync::Once;
+
     use super::*;
 
+    /// A two-section word list (see [`word_source::parse_sections`]) covering
+    /// just the days these tests reference plus `alive` itself, so the suite
+    /// doesn't depend on scraping the live page or on `assets/bundled_words.txt`
+    /// (which only covers the first 10 days) when run with no network access.
+    const TEST_FIXTURE: &str = include_str!("../assets/test_fixture_words.txt");
+
+    /// Points `WORDLE_DATA` at [`TEST_FIXTURE`] instead of the default
+    /// scrape-then-cache source. Idempotent, since `configure_word_source`
+    /// panics if called more than once and every test in this module needs
+    /// to call this before touching `WORDLE_DATA`.
+    fn configure_test_word_source() {
+        static INIT: Once = Once::new();
+        INIT.call_once(|| {
+            let path = std::env::temp_dir().join("wordle-thing-test-fixture.txt");
+            std::fs::write(&path, TEST_FIXTURE).expect("failed to write test fixture");
+            configure_word_source(ResolvedWordSource::new(
+                WordSource::File(path),
+                std::env::temp_dir(),
+                word_source::DEFAULT_CACHE_TTL,
+            ));
+        });
+    }
+
     macro_rules! test {
         (
             $nom:ident,
@@ -417,6 +607,8 @@ mod tests {
         ) => {
             #[tokio::test(flavor = "multi_thread")]
             async fn $nom() {
+                configure_test_word_source();
+
                 let guesses = [$(
                     (
                         TryInto::<[_; 5]>::try_into($g.chars()
@@ -0,0 +1,117 @@
+//! Ranks first-guess candidates by expected information gain.
+//!
+//! `solve` narrows candidates down via past inference, but gives no sense
+//! of which of several remaining candidates is the best *opening* guess.
+//! For each candidate we simulate guessing it against every known answer,
+//! bucket answers by the feedback pattern that guess would produce, and
+//! score the candidate by the Shannon entropy of the bucket sizes — a
+//! higher entropy means the guess splits the answer set more evenly, and
+//! so is expected to rule out more possibilities.
+
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use rayon::prelude::*;
+
+use crate::{Cell, Guess, WORDLE_DATA};
+
+lazy_static! {
+    /// A dedicated thread pool for ranking, sized to the available cores.
+    /// Built once and reused — `interactive` mode calls `rank_candidates`
+    /// once per entered line, and rebuilding a pool on every call would
+    /// throw away its setup cost each time.
+    static ref RANKING_POOL: rayon::ThreadPool = rayon::ThreadPoolBuilder::new()
+        .num_threads(num_cpus::get())
+        .build()
+        .expect("failed to build a rayon thread pool");
+}
+
+/// The feedback pattern `guess` would produce if `answer` were the actual
+/// word, handling repeated letters the same way the real game does (a
+/// letter only counts as partial if the answer has a copy of it that
+/// hasn't already been accounted for by a green or an earlier yellow).
+fn feedback(guess: &str, answer: &str) -> Guess {
+    let guess: [char; 5] = guess.chars().collect::<Vec<_>>().try_into().unwrap();
+    let answer: [char; 5] = answer.chars().collect::<Vec<_>>().try_into().unwrap();
+
+    let mut pattern = [Cell::Nop; 5];
+    let mut unmatched = [0u8; 26];
+    for i in 0..5 {
+        if guess[i] == answer[i] {
+            pattern[i] = Cell::Match;
+        } else {
+            unmatched[(answer[i] as u8 - b'a') as usize] += 1;
+        }
+    }
+
+    for i in 0..5 {
+        if pattern[i] == Cell::Match {
+            continue;
+        }
+
+        let idx = (guess[i] as u8 - b'a') as usize;
+        if unmatched[idx] > 0 {
+            pattern[i] = Cell::Partial;
+            unmatched[idx] -= 1;
+        }
+    }
+
+    pattern
+}
+
+/// The expected information gain (in bits) from guessing `candidate` as an
+/// opener, i.e. the Shannon entropy of the feedback patterns it produces
+/// across every known answer.
+fn entropy(candidate: &str) -> f64 {
+    let mut buckets: HashMap<Guess, usize> = HashMap::new();
+    for answer in &WORDLE_DATA.answers {
+        *buckets.entry(feedback(candidate, answer)).or_insert(0) += 1;
+    }
+
+    let total = WORDLE_DATA.answers.len() as f64;
+    buckets
+        .values()
+        .map(|&count| {
+            let p = count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Ranks `candidates` by expected information gain, highest first.
+pub fn rank_candidates(candidates: &[&'static str]) -> Vec<(&'static str, f64)> {
+    let mut scored: Vec<(&'static str, f64)> = RANKING_POOL.install(|| {
+        candidates
+            .par_iter()
+            .map(|&candidate| (candidate, entropy(candidate)))
+            .collect()
+    });
+
+    scored.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+    scored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feedback_fluff_foggy() {
+        assert_eq!(
+            feedback("fluff", "foggy"),
+            [Cell::Match, Cell::Nop, Cell::Nop, Cell::Nop, Cell::Nop]
+        );
+    }
+
+    /// The guess has three `s`s but the answer only has one (already spent
+    /// on the green at position 3), so the other two guessed `s`s get
+    /// nothing — repeated guess letters don't each get their own share of a
+    /// single repeated answer letter.
+    #[test]
+    fn feedback_guess_repeats_answer_does_not() {
+        assert_eq!(
+            feedback("sassy", "chase"),
+            [Cell::Nop, Cell::Partial, Cell::Nop, Cell::Match, Cell::Nop]
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! Pluggable sources for the valid-word / answer lists `solve` needs.
+//!
+//! The original implementation hard-scraped a specific page layout on
+//! `powerlanguage.co.uk` (the game's original home before it moved to the
+//! NYT) and string-sliced the word arrays out of the bundle — brittle, and
+//! already broken once the page structure changes out from under it.
+//! [`WordSource`] decouples "how do we get the word lists" from the rest of
+//! the tool, and [`ResolvedWordSource`] wraps the configured one, keeping
+//! scraped lists in an on-disk, TTL'd cache (local files are read directly)
+//! so a broken scrape doesn't take the whole tool down with it — this mirrors the session/cookie-caching pattern
+//! competitive-programming scrapers use to stay usable when upstream moves.
+
+use std::{
+    fs,
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, SystemTime},
+};
+
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+use reqwest::Url;
+use soup::{NodeExt, QueryBuilderExt, Soup};
+
+/// A bundled, offline snapshot so the tool still works with no network
+/// access and nothing in the cache yet.
+const BUNDLED_WORD_LIST: &str = include_str!("../assets/bundled_words.txt");
+
+const SCRAPE_URL: &str = "https://www.powerlanguage.co.uk/wordle/";
+
+/// How long a cached word list is trusted before we try to re-scrape.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60 * 60 * 24);
+
+/// Where to load the valid-word / answer lists from.
+#[derive(Debug, Clone)]
+pub enum WordSource {
+    /// Scrape the live Wordle page. Brittle, but always current if it still
+    /// works.
+    Scrape,
+    /// A local two-section file: valid words, a blank line, then answers
+    /// (see [`parse_sections`]).
+    File(PathBuf),
+}
+
+impl FromStr for WordSource {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(if s.eq_ignore_ascii_case("scrape") {
+            WordSource::Scrape
+        } else {
+            WordSource::File(PathBuf::from(s))
+        })
+    }
+}
+
+impl WordSource {
+    async fn fetch(&self) -> Result<(Vec<String>, Vec<String>)> {
+        match self {
+            WordSource::Scrape => scrape().await,
+            WordSource::File(path) => {
+                let text = fs::read_to_string(path).wrap_err_with(|| {
+                    format!("failed to read word list from `{}`", path.display())
+                })?;
+                parse_sections(&text)
+            }
+        }
+    }
+}
+
+/// A [`WordSource`] wrapped in an on-disk cache.
+///
+/// The cache only applies to [`WordSource::Scrape`]: a fresh cache entry is
+/// reused outright, a successful scrape refreshes the cache, and a failed
+/// scrape falls back to whatever's cached (even if stale) and finally to
+/// the bundled snapshot. [`WordSource::File`] is explicit about where its
+/// data comes from, so it bypasses the cache entirely in both directions —
+/// it's never read from the scrape cache, and it never overwrites it.
+pub struct ResolvedWordSource {
+    source: WordSource,
+    cache_path: PathBuf,
+    ttl: Duration,
+}
+
+impl ResolvedWordSource {
+    pub fn new(source: WordSource, cache_dir: PathBuf, ttl: Duration) -> Self {
+        Self {
+            source,
+            cache_path: cache_dir.join("words.txt"),
+            ttl,
+        }
+    }
+
+    pub async fn fetch(&self) -> Result<(Vec<String>, Vec<String>)> {
+        let WordSource::Scrape = &self.source else {
+            return self.source.fetch().await;
+        };
+
+        if let Some(fresh) = self.read_cache(Some(self.ttl)) {
+            return Ok(fresh);
+        }
+
+        match self.source.fetch().await {
+            Ok(data) => {
+                self.write_cache(&data);
+                Ok(data)
+            }
+            Err(scrape_err) => self
+                .read_cache(None)
+                .map(Ok)
+                .or_else(|| parse_sections(BUNDLED_WORD_LIST).ok().map(Ok))
+                .unwrap_or(Err(scrape_err)),
+        }
+    }
+
+    /// Reads the cache file, if present. When `max_age` is `Some`, a cache
+    /// entry older than it is treated as missing.
+    fn read_cache(&self, max_age: Option<Duration>) -> Option<(Vec<String>, Vec<String>)> {
+        let metadata = fs::metadata(&self.cache_path).ok()?;
+        if let Some(max_age) = max_age {
+            let age = SystemTime::now()
+                .duration_since(metadata.modified().ok()?)
+                .ok()?;
+            if age > max_age {
+                return None;
+            }
+        }
+
+        let text = fs::read_to_string(&self.cache_path).ok()?;
+        parse_sections(&text).ok()
+    }
+
+    fn write_cache(&self, data: &(Vec<String>, Vec<String>)) {
+        let Some(parent) = self.cache_path.parent() else { return };
+        if fs::create_dir_all(parent).is_err() {
+            return;
+        }
+
+        let contents = format!("{}\n\n{}\n", data.0.join("\n"), data.1.join("\n"));
+        let _ = fs::write(&self.cache_path, contents);
+    }
+}
+
+impl Default for ResolvedWordSource {
+    fn default() -> Self {
+        Self::new(WordSource::Scrape, default_cache_dir(), DEFAULT_CACHE_TTL)
+    }
+}
+
+/// The platform cache dir (e.g. `~/.cache/wordle-thing` on Linux), falling
+/// back to the system temp dir if it can't be determined.
+pub fn default_cache_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("wordle-thing")
+}
+
+/// Parses the two-section word-list format: valid words, a blank line, then
+/// answers, one word per line. Every entry must be exactly 5 ASCII
+/// lowercase letters.
+///
+/// Splits line-by-line (so CRLF files work the same as LF ones, since
+/// `str::lines` strips either) rather than on a literal `"\n\n"`, and treats
+/// a run of one or more blank lines as a single section break.
+fn parse_sections(text: &str) -> Result<(Vec<String>, Vec<String>)> {
+    let mut lines = text.lines();
+    let valid_words: Vec<&str> = lines.by_ref().take_while(|l| !l.trim().is_empty()).collect();
+    let answers: Vec<&str> = lines.skip_while(|l| l.trim().is_empty()).collect();
+
+    if answers.is_empty() {
+        return Err(eyre!("word list is missing its answers section"));
+    }
+
+    Ok((
+        parse_words(&valid_words.join("\n"))?,
+        parse_words(&answers.join("\n"))?,
+    ))
+}
+
+fn parse_words(section: &str) -> Result<Vec<String>> {
+    section
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|w| {
+            if w.len() == 5 && w.bytes().all(|b| b.is_ascii_lowercase()) {
+                Ok(w.to_string())
+            } else {
+                Err(eyre!("`{w}` is not 5 ASCII lowercase letters"))
+            }
+        })
+        .collect()
+}
+
+async fn scrape() -> Result<(Vec<String>, Vec<String>)> {
+    let url = Url::parse(SCRAPE_URL).unwrap();
+    let main_page = reqwest::get(url.clone())
+        .await
+        .wrap_err("failed to fetch the Wordle page")?
+        .text()
+        .await
+        .wrap_err("failed to read the Wordle page body")?;
+
+    let script = Soup::new(&main_page)
+        .tag("script")
+        .find_all()
+        .filter_map(|script| script.get("src"))
+        .filter(|src| src.starts_with("main"))
+        .last()
+        .ok_or_else(|| eyre!("couldn't find the main script on the Wordle page"))?;
+
+    let script_url = url.join(&script).unwrap();
+    let script = reqwest::get(script_url)
+        .await
+        .wrap_err("failed to fetch the Wordle main script")?
+        .text()
+        .await
+        .wrap_err("failed to read the Wordle main script body")?;
+
+    const DAY1_ANS: &str = "cigar";
+    let answers_starting_idx = script
+        .find(&format!("[\"{DAY1_ANS}\","))
+        .ok_or_else(|| eyre!("couldn't find the answers array in the main script"))?;
+    let data = &script[answers_starting_idx..];
+    let answers_ending_idx = data
+        .find(']')
+        .ok_or_else(|| eyre!("unterminated answers array in the main script"))?;
+    let answers = &data[1..answers_ending_idx];
+
+    let word_list_starting_idx = (&data[1..])
+        .find('[')
+        .ok_or_else(|| eyre!("couldn't find the valid-words array in the main script"))?;
+    let word_list = &data[1 + word_list_starting_idx..];
+    let word_list_ending_idx = word_list
+        .find(']')
+        .ok_or_else(|| eyre!("unterminated valid-words array in the main script"))?;
+    let word_list = &word_list[1..word_list_ending_idx];
+
+    let parse = |s: &str| s.trim_matches('"').to_string();
+
+    let valid_words = parse_words(&word_list.split(',').map(parse).collect::<Vec<_>>().join("\n"))?;
+    let answers = parse_words(&answers.split(',').map(parse).collect::<Vec<_>>().join("\n"))?;
+
+    Ok((valid_words, answers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_sections_round_trip() {
+        let (valid_words, answers) = parse_sections("abcde\nfghij\n\nklmno\npqrst\n").unwrap();
+        assert_eq!(valid_words, ["abcde", "fghij"]);
+        assert_eq!(answers, ["klmno", "pqrst"]);
+    }
+
+    #[test]
+    fn parse_sections_handles_crlf() {
+        let (valid_words, answers) =
+            parse_sections("abcde\r\nfghij\r\n\r\nklmno\r\npqrst\r\n").unwrap();
+        assert_eq!(valid_words, ["abcde", "fghij"]);
+        assert_eq!(answers, ["klmno", "pqrst"]);
+    }
+
+    #[test]
+    fn parse_sections_tolerates_extra_blank_lines() {
+        let (valid_words, answers) = parse_sections("abcde\n\n\n\nklmno\npqrst\n").unwrap();
+        assert_eq!(valid_words, ["abcde"]);
+        assert_eq!(answers, ["klmno", "pqrst"]);
+    }
+
+    #[test]
+    fn parse_sections_missing_answers_errs() {
+        assert!(parse_sections("abcde\nfghij\n").is_err());
+    }
+}